@@ -1,11 +1,14 @@
 use crate::errors::TreeRiteError;
 use crate::sys::{
-    treelite_dmatrix_create_from_array, treelite_dmatrix_create_from_csr_format,
+    treelite_dmatrix_create_from_array, treelite_dmatrix_create_from_array_with_missing,
+    treelite_dmatrix_create_from_csc_format, treelite_dmatrix_create_from_csr_format,
     treelite_dmatrix_create_from_slice, treelite_dmatrix_create_from_slice_with_cols,
-    treelite_dmatrix_free, treelite_dmatrix_get_dimension, DMatrixHandle, FloatInfo,
+    treelite_dmatrix_create_from_slice_with_cols_with_missing,
+    treelite_dmatrix_create_from_slice_with_missing, treelite_dmatrix_free,
+    treelite_dmatrix_get_dimension, DMatrixHandle, FloatInfo,
 };
 
-use fehler::throws;
+use fehler::{throw, throws};
 use ndarray::{AsArray, Ix2};
 use num_traits::Float;
 use std::convert::TryInto;
@@ -17,6 +20,43 @@ pub struct DMatrix<F> {
     _phantom: PhantomData<F>,
 }
 
+/// Options controlling how a dense `DMatrix` is constructed.
+///
+/// The only option today is which value denotes a missing feature: Treelite's dense
+/// constructors need a designated sentinel to tell "feature absent" apart from "feature
+/// equals this real value". Leaving `missing` unset defaults to `NaN`, matching Treelite's
+/// own default.
+#[derive(Clone, Copy, Debug)]
+pub struct DMatrixOptions<F> {
+    missing: Option<F>,
+}
+
+impl<F> Default for DMatrixOptions<F> {
+    fn default() -> Self {
+        DMatrixOptions { missing: None }
+    }
+}
+
+impl<F> DMatrixOptions<F>
+where
+    F: Float,
+{
+    /// Start with no explicit missing-value sentinel (defaults to `NaN` at construction time).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Declare which value should be treated as an absent feature.
+    pub fn missing(mut self, missing: F) -> Self {
+        self.missing = Some(missing);
+        self
+    }
+
+    fn missing_or_nan(&self) -> F {
+        self.missing.unwrap_or_else(F::nan)
+    }
+}
+
 unsafe impl<F> Sync for DMatrix<F> where F: Sync {}
 unsafe impl<F> Send for DMatrix<F> where F: Send {}
 
@@ -25,13 +65,30 @@ where
     F: Float + FloatInfo,
 {
     /// Create a DMatrix from any type that can be converted to a 2d ndarray::ArrayView. This function is zero copy.
+    /// Entries equal to `NaN` are treated as missing features; use [`DMatrix::from_2darray_with_missing`]
+    /// to pick a different sentinel (e.g. when your data encodes missingness as `0.0`).
     #[throws(TreeRiteError)]
     pub fn from_2darray<'a, A>(array: A) -> DMatrix<F>
     where
         A: AsArray<'a, F, Ix2>,
         F: 'a,
     {
-        let handle = treelite_dmatrix_create_from_array(array.into())?;
+        Self::from_2darray_with_missing(array, DMatrixOptions::new())?
+    }
+
+    /// Create a DMatrix from any type that can be converted to a 2d ndarray::ArrayView, treating
+    /// `options`'s missing value (or `NaN` if unset) as an absent feature rather than a real value.
+    /// This function is zero copy.
+    #[throws(TreeRiteError)]
+    pub fn from_2darray_with_missing<'a, A>(array: A, options: DMatrixOptions<F>) -> DMatrix<F>
+    where
+        A: AsArray<'a, F, Ix2>,
+        F: 'a,
+    {
+        let handle = treelite_dmatrix_create_from_array_with_missing(
+            array.into(),
+            options.missing_or_nan(),
+        )?;
         DMatrix {
             handle,
             _phantom: PhantomData,
@@ -39,17 +96,46 @@ where
     }
 
     /// Create a single row DMatrix from a slice of floats. Useful for prediction for a single instance.
-    /// This function is zero copy.
+    /// Entries equal to `NaN` are treated as missing features; use [`DMatrix::from_slice_with_missing`]
+    /// to pick a different sentinel. This function is zero copy.
     #[throws(TreeRiteError)]
     pub fn from_slice(array: &[F]) -> DMatrix<F> {
         array.try_into()?
     }
 
+    /// Create a single row DMatrix from a slice of floats, treating `options`'s missing value
+    /// (or `NaN` if unset) as an absent feature rather than a real value. This function is zero copy.
+    #[throws(TreeRiteError)]
+    pub fn from_slice_with_missing(array: &[F], options: DMatrixOptions<F>) -> DMatrix<F> {
+        let handle = treelite_dmatrix_create_from_slice_with_missing(array, options.missing_or_nan())?;
+        DMatrix {
+            handle,
+            _phantom: PhantomData,
+        }
+    }
+
     /// Create a DMatrix from a slice of floats and a column count.
+    /// Entries equal to `NaN` are treated as missing features; use
+    /// [`DMatrix::from_slice_with_cols_with_missing`] to pick a different sentinel.
     /// This function is zero copy.
     #[throws(TreeRiteError)]
     pub fn from_slice_with_cols(array: &[F], ncols: u64) -> DMatrix<F> {
-        let handle = treelite_dmatrix_create_from_slice_with_cols(array, ncols)?;
+        Self::from_slice_with_cols_with_missing(array, ncols, DMatrixOptions::new())?
+    }
+
+    /// Create a DMatrix from a slice of floats and a column count, treating `options`'s missing
+    /// value (or `NaN` if unset) as an absent feature rather than a real value. This function is zero copy.
+    #[throws(TreeRiteError)]
+    pub fn from_slice_with_cols_with_missing(
+        array: &[F],
+        ncols: u64,
+        options: DMatrixOptions<F>,
+    ) -> DMatrix<F> {
+        let handle = treelite_dmatrix_create_from_slice_with_cols_with_missing(
+            array,
+            ncols,
+            options.missing_or_nan(),
+        )?;
         DMatrix {
             handle,
             _phantom: PhantomData,
@@ -57,7 +143,10 @@ where
     }
 
     /// Create a csr format DMatrix.
-    /// This function is zero copy.
+    ///
+    /// Unlike the dense constructors, there is no missing-value sentinel here: any `(row, col)`
+    /// pair not present in `indices`/`data` is implicitly missing, while a stored `0.0` is a real
+    /// zero. This function is zero copy.
     #[throws(TreeRiteError)]
     pub fn from_csr_format<'a>(
         headers: &'a [u64],
@@ -73,6 +162,47 @@ where
             _phantom: PhantomData,
         }
     }
+
+    /// Create a csc (compressed sparse column) format DMatrix.
+    ///
+    /// As with [`DMatrix::from_csr_format`], any `(row, col)` pair not present in
+    /// `row_indices`/`data` is implicitly missing, while a stored `0.0` is a real zero.
+    /// This function is zero copy.
+    #[throws(TreeRiteError)]
+    pub fn from_csc_format<'a>(
+        col_ptr: &'a [u64],
+        row_indices: &'a [u32],
+        data: &'a [F],
+        num_row: u64,
+        num_col: u64,
+    ) -> DMatrix<F> {
+        match num_col.checked_add(1) {
+            Some(expected) if col_ptr.len() as u64 == expected => {}
+            Some(expected) => throw!(TreeRiteError::InvalidDMatrixShape(format!(
+                "col_ptr.len() ({}) must equal num_col + 1 ({})",
+                col_ptr.len(),
+                expected
+            ))),
+            None => throw!(TreeRiteError::InvalidDMatrixShape(format!(
+                "num_col ({}) is too large: num_col + 1 overflows u64",
+                num_col
+            ))),
+        }
+        if row_indices.len() != data.len() {
+            throw!(TreeRiteError::InvalidDMatrixShape(format!(
+                "row_indices.len() ({}) must equal data.len() ({})",
+                row_indices.len(),
+                data.len()
+            )));
+        }
+
+        let handle =
+            treelite_dmatrix_create_from_csc_format(col_ptr, row_indices, data, num_row, num_col)?;
+        DMatrix {
+            handle,
+            _phantom: PhantomData,
+        }
+    }
 }
 
 impl<F> DMatrix<F> {
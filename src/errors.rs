@@ -0,0 +1,33 @@
+use std::fmt;
+
+/// Errors produced by this crate's safe wrappers over the Treelite C API.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TreeRiteError {
+    /// Treelite's own last-error string, captured via `TreeliteGetLastError`.
+    CError(String),
+    /// A `DataType` string returned by Treelite that this crate doesn't recognize.
+    UnknownDataTypeString(String),
+    /// A `DMatrix` constructor or a chunked prediction call was given inputs whose shapes
+    /// don't agree (e.g. `col_ptr`/`num_col`, `headers`/`num_row`, or `row_indices`/`data`
+    /// length mismatches).
+    InvalidDMatrixShape(String),
+    /// A function was called with an argument outside its valid range.
+    InvalidArgument(String),
+}
+
+impl fmt::Display for TreeRiteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TreeRiteError::CError(msg) => write!(f, "treelite error: {}", msg),
+            TreeRiteError::UnknownDataTypeString(s) => {
+                write!(f, "unknown treelite data type string: {}", s)
+            }
+            TreeRiteError::InvalidDMatrixShape(msg) => {
+                write!(f, "invalid dmatrix shape: {}", msg)
+            }
+            TreeRiteError::InvalidArgument(msg) => write!(f, "invalid argument: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for TreeRiteError {}
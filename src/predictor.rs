@@ -0,0 +1,286 @@
+use crate::dmatrix::{DMatrix, DMatrixOptions};
+use crate::errors::TreeRiteError;
+use crate::sys::{
+    treelite_create_predictor_output_vector, treelite_delete_predictor_output_vector,
+    treelite_predictor_free, treelite_predictor_load, treelite_predictor_predict_batch,
+    treelite_predictor_query_global_bias, treelite_predictor_query_leaf_output_type,
+    treelite_predictor_query_num_class, treelite_predictor_query_num_feature,
+    treelite_predictor_query_pred_transform, treelite_predictor_query_result_size,
+    treelite_predictor_query_sigmoid_alpha, treelite_predictor_query_threshold_type, DataType,
+    FloatInfo, PredictorHandle, PredictorOutputHandle,
+};
+
+use fehler::{throw, throws};
+use ndarray::Array2;
+use num_traits::Float;
+use std::convert::TryInto;
+use std::marker::PhantomData;
+use std::path::Path;
+
+/// Safe wrapper around a loaded Treelite prediction library.
+pub struct Predictor<F> {
+    pub(crate) handle: PredictorHandle,
+    _phantom: PhantomData<F>,
+}
+
+unsafe impl<F> Sync for Predictor<F> where F: Sync {}
+unsafe impl<F> Send for Predictor<F> where F: Send {}
+
+/// Number of rows implied by a dense row-major buffer of `ncols` columns, erroring if `ncols`
+/// is zero or `data_len` is not a multiple of `ncols`. An empty buffer gives zero rows.
+#[throws(TreeRiteError)]
+fn dense_num_rows(data_len: usize, ncols: u64) -> u64 {
+    if ncols == 0 || data_len as u64 % ncols != 0 {
+        throw!(TreeRiteError::InvalidArgument(format!(
+            "data.len() ({}) must be a multiple of ncols ({}), and ncols must be non-zero",
+            data_len, ncols
+        )));
+    }
+    data_len as u64 / ncols
+}
+
+/// Split `num_row` rows into `(start, len)` chunks of at most `rows_per_chunk` rows each, in
+/// order, with the final chunk sized down to whatever remains.
+#[throws(TreeRiteError)]
+fn chunk_ranges(num_row: u64, rows_per_chunk: u64) -> Vec<(u64, u64)> {
+    if rows_per_chunk == 0 {
+        throw!(TreeRiteError::InvalidArgument(
+            "rows_per_chunk must be greater than zero".to_string()
+        ));
+    }
+
+    let mut ranges = Vec::new();
+    let mut start = 0;
+    while start < num_row {
+        let len = rows_per_chunk.min(num_row - start);
+        ranges.push((start, len));
+        start += len;
+    }
+    ranges
+}
+
+impl<F> Predictor<F>
+where
+    F: Float + FloatInfo,
+{
+    /// Load a compiled prediction library from `path`, running with `nthread` worker threads.
+    /// Pass `0` for `nthread` to let Treelite pick a sensible default.
+    #[throws(TreeRiteError)]
+    pub fn load<P: AsRef<Path>>(path: P, nthread: i32) -> Predictor<F> {
+        let handle = treelite_predictor_load(path.as_ref(), nthread)?;
+        Predictor {
+            handle,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Run batch prediction over `dmat`, returning a `num_row x (result_size / num_row)` array,
+    /// i.e. one row of outputs per input row.
+    #[throws(TreeRiteError)]
+    pub fn predict(&self, dmat: &DMatrix<F>) -> Array2<F> {
+        let num_row = dmat.nrows()?;
+        let ncols = self.result_size_per_row(dmat)?;
+
+        let out = treelite_create_predictor_output_vector(self.handle, dmat.handle)?;
+        let written = treelite_predictor_predict_batch(self.handle, dmat.handle, 0, 0, out)?;
+        let mut values = Vec::new();
+        Self::read_output_into(out, written, &mut values);
+        treelite_delete_predictor_output_vector(out)?;
+
+        Array2::from_shape_vec((num_row as usize, ncols as usize), values)
+            .expect("predictor returned a result vector whose size does not match num_row * ncols")
+    }
+
+    /// Treelite always writes prediction output as `float` (`f32`), regardless of `F` — the same
+    /// is true of [`Predictor::global_bias`] and [`Predictor::sigmoid_alpha`] below. Reinterpreting
+    /// the raw buffer directly as `[F]` would read the wrong number of bytes whenever `F` isn't
+    /// `f32`, so read it as `f32` first and convert each value into `F`. `buffer` is cleared and
+    /// refilled in place so callers (e.g. [`Predictor::predict_chunked`]) can reuse one allocation
+    /// across many calls.
+    fn read_output_into(out: PredictorOutputHandle, len: u64, buffer: &mut Vec<F>) {
+        let raw = unsafe { std::slice::from_raw_parts(out as *const f32, len as usize) };
+        buffer.clear();
+        buffer.extend(
+            raw.iter()
+                .map(|&v| F::from(v).expect("predictor output value not representable in F")),
+        );
+    }
+
+    /// Run prediction over a dense, row-major buffer of `ncols` columns, in chunks of
+    /// `rows_per_chunk` rows, invoking `f` with each chunk's starting row offset and its
+    /// flattened output slice. `options`'s missing value (or `NaN` if unset) is applied to
+    /// every chunk, exactly as in [`DMatrix::from_slice_with_cols_with_missing`].
+    ///
+    /// Each chunk is built from its own slice of `data` via that same zero-copy constructor,
+    /// so unlike [`Predictor::predict`], this never allocates input or output for the whole
+    /// matrix up front: a single output buffer is reused across chunks, keeping peak memory
+    /// bounded by `rows_per_chunk` regardless of `data`'s total row count. The final chunk,
+    /// which may have fewer than `rows_per_chunk` rows, is sized down accordingly.
+    ///
+    /// For CSR-backed data, see [`Predictor::predict_chunked_csr`]. There is no chunked entry
+    /// point for CSC-backed matrices: unlike CSR, a CSC buffer can't be sliced by row range
+    /// without reshuffling column pointers, so [`DMatrix::from_csc_format`] users must go
+    /// through [`Predictor::predict`] instead.
+    #[throws(TreeRiteError)]
+    pub fn predict_chunked(
+        &self,
+        data: &[F],
+        ncols: u64,
+        options: DMatrixOptions<F>,
+        rows_per_chunk: u64,
+        mut f: impl FnMut(u64, &[F]),
+    ) {
+        let num_row = dense_num_rows(data.len(), ncols)?;
+        let mut buffer = Vec::new();
+
+        for (start, len) in chunk_ranges(num_row, rows_per_chunk)? {
+            let chunk_data = &data[(start * ncols) as usize..((start + len) * ncols) as usize];
+            let chunk = DMatrix::from_slice_with_cols_with_missing(chunk_data, ncols, options)?;
+
+            let out = treelite_create_predictor_output_vector(self.handle, chunk.handle)?;
+            let written = treelite_predictor_predict_batch(self.handle, chunk.handle, 0, 0, out)?;
+            Self::read_output_into(out, written, &mut buffer);
+            treelite_delete_predictor_output_vector(out)?;
+
+            f(start, &buffer);
+        }
+    }
+
+    /// Run prediction over a CSR-format sparse matrix in chunks of `rows_per_chunk` rows,
+    /// invoking `f` with each chunk's starting row offset and its flattened output slice.
+    ///
+    /// Each chunk re-bases the relevant slice of `headers` to start at zero and builds its own
+    /// zero-copy [`DMatrix::from_csr_format`] over that chunk's slice of `indices`/`data`, so
+    /// only `rows_per_chunk` rows' worth of input and output are ever live at once.
+    #[throws(TreeRiteError)]
+    pub fn predict_chunked_csr(
+        &self,
+        headers: &[u64],
+        indices: &[u32],
+        data: &[F],
+        num_row: u64,
+        num_col: u64,
+        rows_per_chunk: u64,
+        mut f: impl FnMut(u64, &[F]),
+    ) {
+        if headers.len() as u64 != num_row + 1 {
+            throw!(TreeRiteError::InvalidDMatrixShape(format!(
+                "headers.len() ({}) must equal num_row + 1 ({})",
+                headers.len(),
+                num_row + 1
+            )));
+        }
+
+        let mut buffer = Vec::new();
+
+        for (start, len) in chunk_ranges(num_row, rows_per_chunk)? {
+            let row_start = headers[start as usize];
+            let row_end = headers[(start + len) as usize];
+            let chunk_headers: Vec<u64> = headers[start as usize..=(start + len) as usize]
+                .iter()
+                .map(|h| h - row_start)
+                .collect();
+            let chunk_indices = &indices[row_start as usize..row_end as usize];
+            let chunk_data = &data[row_start as usize..row_end as usize];
+            let chunk =
+                DMatrix::from_csr_format(&chunk_headers, chunk_indices, chunk_data, len, num_col)?;
+
+            let out = treelite_create_predictor_output_vector(self.handle, chunk.handle)?;
+            let written = treelite_predictor_predict_batch(self.handle, chunk.handle, 0, 0, out)?;
+            Self::read_output_into(out, written, &mut buffer);
+            treelite_delete_predictor_output_vector(out)?;
+
+            f(start, &buffer);
+        }
+    }
+
+    /// Per-row width of `dmat`'s prediction output: the total result size for `dmat` divided by
+    /// its row count, or `num_class` (at least `1`) when `dmat` has no rows.
+    #[throws(TreeRiteError)]
+    fn result_size_per_row(&self, dmat: &DMatrix<F>) -> u64 {
+        let num_row = dmat.nrows()?;
+        let result_size = treelite_predictor_query_result_size(self.handle, dmat.handle)?;
+        if num_row > 0 {
+            result_size / num_row
+        } else {
+            self.num_class()?.max(1)
+        }
+    }
+
+    /// Number of input features the model expects.
+    #[throws(TreeRiteError)]
+    pub fn num_feature(&self) -> u64 {
+        treelite_predictor_query_num_feature(self.handle)?
+    }
+
+    /// Number of output classes of the model (`1` for regression and binary classification).
+    #[throws(TreeRiteError)]
+    pub fn num_class(&self) -> u64 {
+        treelite_predictor_query_num_class(self.handle)?
+    }
+
+    /// Global bias added to every prediction before `pred_transform` is applied.
+    #[throws(TreeRiteError)]
+    pub fn global_bias(&self) -> f32 {
+        treelite_predictor_query_global_bias(self.handle)?
+    }
+
+    /// Alpha value used by the model's sigmoid transform, if any.
+    #[throws(TreeRiteError)]
+    pub fn sigmoid_alpha(&self) -> f32 {
+        treelite_predictor_query_sigmoid_alpha(self.handle)?
+    }
+
+    /// Name of the transform applied to raw margin scores (e.g. `"sigmoid"`, `"identity"`).
+    #[throws(TreeRiteError)]
+    pub fn pred_transform(&self) -> String {
+        treelite_predictor_query_pred_transform(self.handle)?
+    }
+
+    /// Data type used to store split thresholds in the compiled model.
+    #[throws(TreeRiteError)]
+    pub fn threshold_type(&self) -> DataType {
+        treelite_predictor_query_threshold_type(self.handle)?.try_into()?
+    }
+
+    /// Data type used to store leaf outputs in the compiled model.
+    #[throws(TreeRiteError)]
+    pub fn leaf_output_type(&self) -> DataType {
+        treelite_predictor_query_leaf_output_type(self.handle)?.try_into()?
+    }
+}
+
+impl<F> Drop for Predictor<F> {
+    fn drop(&mut self) {
+        match treelite_predictor_free(self.handle) {
+            Ok(()) => {}
+            Err(e) => {
+                if cfg!(feature = "free_panic") {
+                    panic!("cannot free predictor: {}", e)
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chunk_ranges_rejects_zero_rows_per_chunk() {
+        assert!(chunk_ranges(10, 0).is_err());
+    }
+
+    #[test]
+    fn dense_num_rows_rejects_non_divisor_ncols() {
+        let data_len = 10;
+        let ncols = 3;
+        assert!(dense_num_rows(data_len, ncols).is_err());
+    }
+
+    #[test]
+    fn chunk_ranges_sizes_down_final_partial_chunk() {
+        assert_eq!(chunk_ranges(5, 2).unwrap(), vec![(0, 2), (2, 2), (4, 1)]);
+    }
+}
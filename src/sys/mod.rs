@@ -5,9 +5,12 @@ mod predictor;
 pub use self::bindings::{DMatrixHandle, PredictorHandle, PredictorOutputHandle};
 use self::bindings::{TreeliteGetLastError, TreeliteRegisterLogCallback};
 pub use self::dmatrix::{
-    treelite_dmatrix_create_from_array, treelite_dmatrix_create_from_csr_format,
+    treelite_dmatrix_create_from_array, treelite_dmatrix_create_from_array_with_missing,
+    treelite_dmatrix_create_from_csc_format, treelite_dmatrix_create_from_csr_format,
     treelite_dmatrix_create_from_slice, treelite_dmatrix_create_from_slice_with_cols,
-    treelite_dmatrix_free, treelite_dmatrix_get_dimension, FloatInfo,
+    treelite_dmatrix_create_from_slice_with_cols_with_missing,
+    treelite_dmatrix_create_from_slice_with_missing, treelite_dmatrix_free,
+    treelite_dmatrix_get_dimension, FloatInfo,
 };
 pub use self::predictor::{
     treelite_create_predictor_output_vector, treelite_delete_predictor_output_vector,